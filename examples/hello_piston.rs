@@ -22,40 +22,24 @@ fn main() {
         });
 
         if e.press_args().is_some() {
-            InnerApp {
-                title: "Inner loop (press X to exit inner loop)",
-                exit_button: Button::Keyboard(Key::X),
-            }
-            .run(&mut window);
-            window.set_title(title.into());
-        }
-    }
-}
-
-/// Stores application state of inner event loop.
-pub struct InnerApp {
-    pub title: &'static str,
-    pub exit_button: Button,
-}
-
-impl InnerApp {
-    pub fn run(&mut self, window: &mut PistonWindow) {
-        window.set_title(self.title.into());
-        while let Some(e) = window.next() {
-            window.draw_2d(&e, |c, g, _| {
-                clear([0.5, 0.5, 1.0, 1.0], g);
-                ellipse(
-                    [1.0, 0.0, 0.0, 1.0],
-                    [50.0, 50.0, 100.0, 100.0],
-                    c.transform,
-                    g,
-                );
-            });
-            if let Some(button) = e.press_args() {
-                if button == self.exit_button {
-                    break;
+            window.set_title("Inner loop (press X to exit inner loop)".into());
+            SubLoop::new().run(&mut window, |window, e| {
+                window.draw_2d(e, |c, g, _| {
+                    clear([0.5, 0.5, 1.0, 1.0], g);
+                    ellipse(
+                        [1.0, 0.0, 0.0, 1.0],
+                        [50.0, 50.0, 100.0, 100.0],
+                        c.transform,
+                        g,
+                    );
+                });
+                if e.press_args() == Some(Button::Keyboard(Key::X)) {
+                    SubLoopControl::Break
+                } else {
+                    SubLoopControl::Continue
                 }
-            }
+            });
+            window.set_title(title.into());
         }
     }
 }
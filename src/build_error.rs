@@ -0,0 +1,41 @@
+//! Errors when building a window.
+
+use piston::window::Api;
+use std::error::Error;
+use std::fmt;
+
+/// An error from `PistonWindow::build_from_window_settings`.
+///
+/// `BuildFromWindowSettings` returns `Box<dyn Error>`,
+/// so use `err.downcast_ref::<PistonWindowBuildError>()` to match on the cause.
+#[derive(Debug)]
+pub enum PistonWindowBuildError {
+    /// The graphics API is not an OpenGL version supported by Gfx.
+    UnsupportedApi(Api),
+    /// The window backend failed to create the window or OpenGL context.
+    WindowCreationFailed(Box<dyn Error>),
+}
+
+impl fmt::Display for PistonWindowBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PistonWindowBuildError::UnsupportedApi(ref api) => write!(
+                f,
+                "Could not detect OpenGL version from graphics API {} {}.{}",
+                api.api, api.major, api.minor
+            ),
+            PistonWindowBuildError::WindowCreationFailed(ref err) => {
+                write!(f, "Could not create window: {}", err)
+            }
+        }
+    }
+}
+
+impl Error for PistonWindowBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            PistonWindowBuildError::UnsupportedApi(_) => None,
+            PistonWindowBuildError::WindowCreationFailed(ref err) => Some(&**err),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Scissor clipping helpers.
+
+use graphics::{Context, Transformed};
+
+/// Produces a context clipped to a rectangle, for drawing UI widgets.
+///
+/// - `window_size` is the size of the frame buffer in pixels
+/// - `clip_rect` is `[x, y, w, h]` in window coordinates
+///
+/// The clip rectangle is intersected with the scissor already set on the context.
+/// Returns the clipped context together with the scissor rectangle
+/// `[x, y, w, h]` in pixels, with origin in the upper left corner.
+///
+/// The scissor is set on `c.draw_state`,
+/// so it applies to draw calls that use the draw state of the context.
+/// Contexts for rendering into textures are flipped vertically,
+/// which is detected from the view transform.
+pub fn scissor_clip_context(
+    mut c: Context,
+    window_size: [u32; 2],
+    clip_rect: [f64; 4],
+) -> (Context, [u32; 4]) {
+    // Scale from window coordinates to pixels.
+    let (sx, sy) = match c.viewport {
+        Some(v) => (
+            f64::from(v.draw_size[0]) / v.window_size[0],
+            f64::from(v.draw_size[1]) / v.window_size[1],
+        ),
+        None => (1.0, 1.0),
+    };
+    let [w, h] = window_size;
+    let clamp = |val: f64, max: u32| val.max(0.0).min(f64::from(max)) as u32;
+    let x0 = clamp((clip_rect[0] * sx).floor(), w);
+    let y0 = clamp((clip_rect[1] * sy).floor(), h);
+    let x1 = clamp(((clip_rect[0] + clip_rect[2]) * sx).ceil(), w);
+    let y1 = clamp(((clip_rect[1] + clip_rect[3]) * sy).ceil(), h);
+    let mut rect = [x0, y0, x1.saturating_sub(x0), y1.saturating_sub(y0)];
+
+    // Scissors use rows from the bottom, which are already flipped in textures.
+    let flipped = is_flipped(&c);
+    let to_scissor = |rect| if flipped { rect } else { flip_y(rect, h) };
+    if let Some(scissor) = c.draw_state.scissor {
+        rect = intersect(rect, to_scissor(scissor));
+    }
+    c.draw_state.scissor = Some(to_scissor(rect));
+    (c, rect)
+}
+
+/// Flips a context vertically, for rendering into a texture of the given height.
+///
+/// Render targets store the bottom row first,
+/// so without flipping, the drawing would be upside down in the texture.
+pub(crate) fn flip_context(mut c: Context, height: u32) -> Context {
+    c.view = c.view.trans(0.0, f64::from(height)).flip_v();
+    c.transform = c.view;
+    c
+}
+
+/// Returns `true` if the view of the context is flipped vertically.
+fn is_flipped(c: &Context) -> bool {
+    // The view maps window coordinates with `y` pointing down
+    // to normalized coordinates with `y` pointing up.
+    c.view[1][1] > 0.0
+}
+
+/// Flips a rectangle between origin in the upper left corner
+/// and origin in the lower left corner, as used by OpenGL scissors.
+pub(crate) fn flip_y(rect: [u32; 4], height: u32) -> [u32; 4] {
+    let y = height.saturating_sub(rect[1] + rect[3]);
+    [rect[0], y, rect[2], rect[3]]
+}
+
+/// Returns the intersection of two rectangles.
+pub(crate) fn intersect(a: [u32; 4], b: [u32; 4]) -> [u32; 4] {
+    let x0 = a[0].max(b[0]);
+    let y0 = a[1].max(b[1]);
+    let x1 = a[0].saturating_add(a[2]).min(b[0].saturating_add(b[2]));
+    let y1 = a[1].saturating_add(a[3]).min(b[1].saturating_add(b[3]));
+    [x0, y0, x1.saturating_sub(x0), y1.saturating_sub(y0)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphics::Viewport;
+
+    fn context(window_size: [f64; 2], draw_size: [u32; 2]) -> Context {
+        Context::new_viewport(Viewport {
+            rect: [0, 0, draw_size[0] as i32, draw_size[1] as i32],
+            window_size,
+            draw_size,
+        })
+    }
+
+    #[test]
+    fn hidpi_viewport() {
+        let c = context([100.0, 100.0], [200, 200]);
+        let (c, rect) = scissor_clip_context(c, [200, 200], [10.0, 10.0, 20.0, 20.0]);
+        assert_eq!(rect, [20, 20, 40, 40]);
+        assert_eq!(c.draw_state.scissor, Some([20, 140, 40, 40]));
+    }
+
+    #[test]
+    fn partly_off_screen() {
+        let c = context([100.0, 100.0], [100, 100]);
+        let (c, rect) = scissor_clip_context(c, [100, 100], [-10.0, 90.0, 30.0, 30.0]);
+        assert_eq!(rect, [0, 90, 20, 10]);
+        assert_eq!(c.draw_state.scissor, Some([0, 0, 20, 10]));
+    }
+
+    #[test]
+    fn fully_off_screen() {
+        let c = context([100.0, 100.0], [100, 100]);
+        let (_, rect) = scissor_clip_context(c, [100, 100], [150.0, 150.0, 10.0, 10.0]);
+        assert_eq!(rect[2], 0);
+        assert_eq!(rect[3], 0);
+    }
+
+    #[test]
+    fn fractional_rect_covers_partial_pixels() {
+        let c = context([100.0, 100.0], [100, 100]);
+        let (_, rect) = scissor_clip_context(c, [100, 100], [10.5, 10.5, 10.0, 10.0]);
+        assert_eq!(rect, [10, 10, 11, 11]);
+    }
+
+    #[test]
+    fn intersects_existing_scissor() {
+        let mut c = context([100.0, 100.0], [100, 100]);
+        // Lower left quarter, with origin in the lower left corner.
+        c.draw_state.scissor = Some([0, 0, 50, 50]);
+        let (c, rect) = scissor_clip_context(c, [100, 100], [25.0, 25.0, 50.0, 50.0]);
+        assert_eq!(rect, [25, 50, 25, 25]);
+        assert_eq!(c.draw_state.scissor, Some([25, 25, 25, 25]));
+    }
+
+    #[test]
+    fn flipped_context_does_not_flip_scissor() {
+        let c = flip_context(context([100.0, 100.0], [100, 100]), 100);
+        let (c, rect) = scissor_clip_context(c, [100, 100], [10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(rect, [10, 20, 30, 40]);
+        assert_eq!(c.draw_state.scissor, Some([10, 20, 30, 40]));
+    }
+
+    #[test]
+    fn flip_context_maps_top_to_first_row() {
+        use graphics::math::transform_pos;
+
+        let c = flip_context(context([100.0, 100.0], [100, 100]), 100);
+        // Normalized `y = -1` is the first row of the render target.
+        assert_eq!(transform_pos(c.transform, [0.0, 0.0]), [-1.0, -1.0]);
+        assert_eq!(transform_pos(c.transform, [100.0, 100.0]), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn flip_y_round_trip() {
+        let rect = [10, 20, 30, 40];
+        assert_eq!(flip_y(rect, 100), [10, 40, 30, 40]);
+        assert_eq!(flip_y(flip_y(rect, 100), 100), rect);
+    }
+
+    #[test]
+    fn intersect_disjoint_and_saturating() {
+        assert_eq!(intersect([0, 0, 10, 10], [20, 20, 5, 5])[2..], [0, 0]);
+        assert_eq!(
+            intersect([0, 0, u32::MAX, u32::MAX], [10, 10, 5, 5]),
+            [10, 10, 5, 5]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Depth textures.
+
+use gfx::format::{DepthStencil, D24_S8};
+use gfx::handle::{ShaderResourceView, Texture};
+use gfx_device_gl::{Factory, Resources};
+use offscreen::StencilView;
+use prelude::TextureError;
+use textures::texture_size;
+
+/// A depth stencil buffer that can also be sampled in shaders.
+///
+/// Create one with `PistonWindow::create_depth_texture`
+/// and keep it sized to the window with `PistonWindow::resize_depth_texture`.
+#[derive(Clone)]
+pub struct DepthTexture {
+    /// The depth stencil texture.
+    pub surface: Texture<Resources, D24_S8>,
+    /// View for sampling the depth values in shaders.
+    pub view: ShaderResourceView<Resources, f32>,
+    /// View for depth testing, used as render target.
+    pub target: StencilView,
+    width: u32,
+    height: u32,
+}
+
+impl DepthTexture {
+    /// Creates a new depth texture.
+    pub(crate) fn new(
+        factory: &mut Factory,
+        width: u32,
+        height: u32,
+    ) -> Result<DepthTexture, TextureError> {
+        use gfx::Factory;
+
+        let (w, h) = texture_size(width, height)?;
+        let (surface, view, target) = factory
+            .create_depth_stencil::<DepthStencil>(w, h)
+            .map_err(TextureError::Create)?;
+        Ok(DepthTexture {
+            surface,
+            view,
+            target,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the size `[width, height]` in pixels.
+    pub fn size(&self) -> [u32; 2] {
+        [self.width, self.height]
+    }
+
+    /// Recreates the texture when the size changes.
+    ///
+    /// Returns `true` if the texture was recreated.
+    pub(crate) fn resize(
+        &mut self,
+        factory: &mut Factory,
+        width: u32,
+        height: u32,
+    ) -> Result<bool, TextureError> {
+        if self.size() == [width, height] {
+            return Ok(false);
+        }
+        *self = DepthTexture::new(factory, width, height)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,119 @@
+//! Filters for suppressing events.
+
+use piston::input::{Button, ButtonArgs, Event, Input, Motion};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Decides whether an event is passed on, by returning `true`, or dropped.
+pub type EventFilterFn = Box<dyn Fn(&Event) -> bool>;
+
+/// Constructors for common event filters.
+///
+/// Pass the filter to `PistonWindow::set_event_filter`.
+pub struct EventFilter;
+
+impl EventFilter {
+    /// Limits mouse move events to at most `max_hz` events per second.
+    ///
+    /// Applies to mouse cursor and relative motion,
+    /// while scroll and other events are passed through.
+    /// When `max_hz` is zero, negative or NaN, all mouse move events are dropped.
+    /// Rates too small to represent the interval allow only the first move.
+    ///
+    /// Moves within the interval are dropped, not delayed,
+    /// because a filter can not emit events later.
+    /// When the mouse stops moving within the interval,
+    /// the last cursor position is never seen,
+    /// and relative motion of the dropped events is lost.
+    /// Do not use this filter when the exact final position matters,
+    /// for example for dragging.
+    pub fn allow_mouse_move_hz(max_hz: f64) -> EventFilterFn {
+        // Saturates when the interval is too long to represent.
+        let interval = if max_hz > 0.0 {
+            Some(Duration::try_from_secs_f64(1.0 / max_hz).unwrap_or(Duration::MAX))
+        } else {
+            None
+        };
+        let last: Cell<Option<Instant>> = Cell::new(None);
+        Box::new(move |e| match *e {
+            Event::Input(Input::Move(Motion::MouseCursor(_)), _)
+            | Event::Input(Input::Move(Motion::MouseRelative(_)), _) => {
+                let interval = match interval {
+                    Some(interval) => interval,
+                    None => return false,
+                };
+                let now = Instant::now();
+                match last.get() {
+                    Some(t) if now.duration_since(t) < interval => false,
+                    _ => {
+                        last.set(Some(now));
+                        true
+                    }
+                }
+            }
+            _ => true,
+        })
+    }
+
+    /// Passes keyboard and text events only.
+    ///
+    /// Resize, focus and close events of the window are passed through,
+    /// while mouse, controller, file drag and custom events are dropped.
+    pub fn keyboard_only() -> EventFilterFn {
+        Box::new(|e| {
+            matches!(
+                *e,
+                Event::Input(
+                    Input::Button(ButtonArgs {
+                        button: Button::Keyboard(_),
+                        ..
+                    }) | Input::Text(_)
+                        | Input::Resize(_)
+                        | Input::Focus(_)
+                        | Input::Close(_),
+                    _
+                )
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse_move() -> Event {
+        Event::Input(Input::Move(Motion::MouseCursor([1.0, 2.0])), None)
+    }
+
+    #[test]
+    fn mouse_move_hz_invalid_drops_moves() {
+        for &hz in &[0.0, -1.0, f64::NAN] {
+            let filter = EventFilter::allow_mouse_move_hz(hz);
+            assert!(!filter(&mouse_move()));
+        }
+    }
+
+    #[test]
+    fn mouse_move_hz_tiny_allows_first_move() {
+        let filter = EventFilter::allow_mouse_move_hz(1e-320);
+        assert!(filter(&mouse_move()));
+        assert!(!filter(&mouse_move()));
+    }
+
+    #[test]
+    fn mouse_move_hz_throttles_moves() {
+        let filter = EventFilter::allow_mouse_move_hz(1.0);
+        assert!(filter(&mouse_move()));
+        assert!(!filter(&mouse_move()));
+        let scroll = Event::Input(Input::Move(Motion::MouseScroll([0.0, 1.0])), None);
+        assert!(filter(&scroll));
+    }
+
+    #[test]
+    fn mouse_move_hz_infinite_passes_moves() {
+        let filter = EventFilter::allow_mouse_move_hz(f64::INFINITY);
+        assert!(filter(&mouse_move()));
+        assert!(filter(&mouse_move()));
+    }
+}
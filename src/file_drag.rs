@@ -0,0 +1,36 @@
+//! File drag and drop events.
+
+use piston::input::{Event, FileDrag, Input};
+use std::path::PathBuf;
+
+/// When a file is dragged or dropped over the window.
+pub trait FileDragEvent: Sized {
+    /// Calls closure if this is a file drag event.
+    fn file_drag<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnMut(&FileDrag) -> U;
+    /// Returns file drag arguments.
+    fn file_drag_args(&self) -> Option<FileDrag> {
+        self.file_drag(|file_drag| file_drag.clone())
+    }
+    /// Returns the path of a file dropped into the window.
+    fn dropped_file_args(&self) -> Option<PathBuf> {
+        self.file_drag(|file_drag| match *file_drag {
+            FileDrag::Drop(ref path) => Some(path.clone()),
+            _ => None,
+        })
+        .and_then(|path| path)
+    }
+}
+
+impl FileDragEvent for Event {
+    fn file_drag<U, F>(&self, mut f: F) -> Option<U>
+    where
+        F: FnMut(&FileDrag) -> U,
+    {
+        match *self {
+            Event::Input(Input::FileDrag(ref file_drag), _) => Some(f(file_drag)),
+            _ => None,
+        }
+    }
+}
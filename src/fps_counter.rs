@@ -0,0 +1,99 @@
+//! Measuring the frame rate.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The number of frames to average the frame rate over.
+const FRAMES: usize = 60;
+
+/// Measures frame durations between after render events.
+pub(crate) struct FpsCounter {
+    last_frame: Option<Instant>,
+    frame_times: VecDeque<Duration>,
+}
+
+impl FpsCounter {
+    pub fn new() -> FpsCounter {
+        FpsCounter {
+            last_frame: None,
+            frame_times: VecDeque::with_capacity(FRAMES),
+        }
+    }
+
+    /// Records the end of a frame.
+    pub fn tick(&mut self) {
+        self.tick_at(Instant::now());
+    }
+
+    /// Records the end of a frame at the given time.
+    fn tick_at(&mut self, now: Instant) {
+        if let Some(last_frame) = self.last_frame {
+            if self.frame_times.len() == FRAMES {
+                self.frame_times.pop_front();
+            }
+            self.frame_times.push_back(now - last_frame);
+        }
+        self.last_frame = Some(now);
+    }
+
+    /// Returns the average frames per second, or `0.0` before two frames are rendered.
+    pub fn fps(&self) -> f64 {
+        let total: Duration = self.frame_times.iter().sum();
+        if total > Duration::from_secs(0) {
+            self.frame_times.len() as f64 / total.as_secs_f64()
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the duration of the last frame.
+    pub fn last_frame_time(&self) -> Duration {
+        self.frame_times.back().cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn zero_before_two_frames() {
+        let mut counter = FpsCounter::new();
+        assert_eq!(counter.fps(), 0.0);
+        counter.tick_at(Instant::now());
+        assert_eq!(counter.fps(), 0.0);
+        assert_eq!(counter.last_frame_time(), Duration::default());
+    }
+
+    #[test]
+    fn averages_frame_times() {
+        let mut counter = FpsCounter::new();
+        let start = Instant::now();
+        counter.tick_at(start);
+        counter.tick_at(start + ms(10));
+        counter.tick_at(start + ms(40));
+        // Two frames in 40 milliseconds.
+        assert!((counter.fps() - 50.0).abs() < 1e-9);
+        assert_eq!(counter.last_frame_time(), ms(30));
+    }
+
+    #[test]
+    fn evicts_oldest_frames() {
+        let mut counter = FpsCounter::new();
+        let start = Instant::now();
+        counter.tick_at(start);
+        // One slow frame, followed by a full window of fast frames.
+        let mut now = start + ms(1000);
+        counter.tick_at(now);
+        for _ in 0..FRAMES {
+            now += ms(10);
+            counter.tick_at(now);
+        }
+        assert_eq!(counter.frame_times.len(), FRAMES);
+        assert!((counter.fps() - 100.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,147 @@
+//! Extra methods for the glyph cache.
+
+use gfx_device_gl::Device;
+use graphics::character::CharacterCache;
+use graphics::glyph_cache::rusttype::GlyphCache;
+use prelude::{submit, Glyphs, TextureError};
+use rusttype::Scale;
+use texture::{CreateTexture, ImageSize, UpdateTexture};
+
+/// Extra methods for `Glyphs`.
+pub trait GlyphsExt {
+    /// Rasterizes and uploads glyphs for the given characters and font size.
+    ///
+    /// Call this before the first frame to avoid stutter
+    /// when characters are drawn for the first time.
+    /// The uploads are submitted to the device before returning.
+    fn preload_range<I>(
+        &mut self,
+        chars: I,
+        size: u32,
+        device: &mut Device,
+    ) -> Result<(), TextureError>
+    where
+        I: Iterator<Item = char>;
+
+    /// Returns `[width, height]` of a line of text in pixels, without rendering it.
+    ///
+    /// The width is the sum of advance widths, and the height is the line height,
+    /// from the ascent to the descent of the font.
+    /// Missing glyphs are loaded into the cache, the same way as when drawing,
+    /// and are submitted with the next frame drawn with `draw_2d_with_glyphs`.
+    fn measure_text(&mut self, text: &str, size: u32) -> Result<[f64; 2], TextureError>;
+
+    /// Preloads the printable ASCII characters, from `' '` to `'~'`.
+    fn preload_ascii(&mut self, size: u32, device: &mut Device) -> Result<(), TextureError>;
+}
+
+impl GlyphsExt for Glyphs {
+    fn preload_range<I>(
+        &mut self,
+        chars: I,
+        size: u32,
+        device: &mut Device,
+    ) -> Result<(), TextureError>
+    where
+        I: Iterator<Item = char>,
+    {
+        self.preload_chars(size, chars)?;
+        submit(&mut self.factory.encoder, device)
+            .map_err(|err| TextureError::Image(err.to_string()))
+    }
+
+    fn preload_ascii(&mut self, size: u32, device: &mut Device) -> Result<(), TextureError> {
+        self.preload_printable_ascii(size)?;
+        submit(&mut self.factory.encoder, device)
+            .map_err(|err| TextureError::Image(err.to_string()))
+    }
+
+    fn measure_text(&mut self, text: &str, size: u32) -> Result<[f64; 2], TextureError> {
+        measure_text(self, text, size)
+    }
+}
+
+/// Measures text with the same scale as the glyph cache uses for drawing.
+fn measure_text<F, T>(
+    cache: &mut GlyphCache<F, T>,
+    text: &str,
+    size: u32,
+) -> Result<[f64; 2], T::Error>
+where
+    T: CreateTexture<F> + UpdateTexture<F> + ImageSize,
+{
+    let width = cache.width(size, text)?;
+    // The glyph cache converts the font size from points to pixels.
+    let px = (size as f32 * 1.333).round();
+    let v_metrics = cache.font.v_metrics(Scale::uniform(px));
+    let height = f64::from(v_metrics.ascent - v_metrics.descent);
+    Ok([width, height])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texture::{Format, TextureOp, TextureSettings};
+
+    /// Texture that stores only its size, for using the glyph cache without a GPU.
+    struct SizeTexture([u32; 2]);
+
+    impl ImageSize for SizeTexture {
+        fn get_size(&self) -> (u32, u32) {
+            (self.0[0], self.0[1])
+        }
+    }
+
+    impl TextureOp<()> for SizeTexture {
+        type Error = ();
+    }
+
+    impl CreateTexture<()> for SizeTexture {
+        fn create<S: Into<[u32; 2]>>(
+            _: &mut (),
+            _: Format,
+            _: &[u8],
+            size: S,
+            _: &TextureSettings,
+        ) -> Result<Self, ()> {
+            Ok(SizeTexture(size.into()))
+        }
+    }
+
+    impl UpdateTexture<()> for SizeTexture {
+        fn update<O, S>(&mut self, _: &mut (), _: Format, _: &[u8], _: O, _: S) -> Result<(), ()>
+        where
+            O: Into<[u32; 2]>,
+            S: Into<[u32; 2]>,
+        {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn height_uses_glyph_cache_scale() {
+        let font = include_bytes!("../assets/Cantarell-Regular.ttf");
+        let mut cache: GlyphCache<(), SizeTexture> =
+            GlyphCache::from_bytes(font, (), TextureSettings::new()).unwrap();
+        let size = 24;
+        let [width, height] = measure_text(&mut cache, "M", size).unwrap();
+
+        // Recover the pixel scale used by the glyph cache from the advance width.
+        let unit_advance = cache
+            .font
+            .glyph('M')
+            .scaled(Scale::uniform(1.0))
+            .h_metrics()
+            .advance_width;
+        let px = width as f32 / unit_advance;
+        let v_metrics = cache.font.v_metrics(Scale::uniform(px));
+        let expected = f64::from(v_metrics.ascent - v_metrics.descent);
+        assert!(
+            (height - expected).abs() < 1e-3,
+            "{} != {}",
+            height,
+            expected
+        );
+        assert!(height > f64::from(size));
+    }
+}
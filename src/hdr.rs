@@ -0,0 +1,64 @@
+//! Floating point textures for HDR images.
+
+use gfx::format::{Rgba32F, R32_G32_B32_A32};
+use gfx::handle::{Sampler, ShaderResourceView, Texture};
+use gfx_device_gl::{Factory, Resources};
+use prelude::{TextureError, TextureSettings};
+use sampler::sampler_info;
+use std::path::Path;
+use textures::texture_size;
+
+/// A texture storing 32 bit floating point colors.
+///
+/// Used for HDR images such as skyboxes and light probes in 3D rendering.
+/// Create one with `PistonWindow::load_texture_hdr`.
+#[derive(Clone, Debug)]
+pub struct HdrTexture {
+    /// Pixel storage for texture.
+    pub surface: Texture<Resources, R32_G32_B32_A32>,
+    /// Sampler for texture.
+    pub sampler: Sampler<Resources>,
+    /// View used by shader.
+    pub view: ShaderResourceView<Resources, [f32; 4]>,
+}
+
+impl HdrTexture {
+    /// Loads an image file, such as Radiance HDR or OpenEXR, into a floating point texture.
+    ///
+    /// Images in other formats are converted to floating point colors.
+    pub fn from_path<P: AsRef<Path>>(
+        factory: &mut Factory,
+        path: P,
+        settings: &TextureSettings,
+    ) -> Result<HdrTexture, TextureError> {
+        use gfx::texture::{AaMode, Kind, Mipmap};
+        use gfx::Factory;
+
+        let img = image::open(path)
+            .map_err(|err| TextureError::Image(err.to_string()))?
+            .to_rgba32f();
+        let (w, h) = img.dimensions();
+        let (w, h) = texture_size(w, h)?;
+        let data: Vec<[u32; 4]> = img
+            .pixels()
+            .map(|p| {
+                [
+                    p[0].to_bits(),
+                    p[1].to_bits(),
+                    p[2].to_bits(),
+                    p[3].to_bits(),
+                ]
+            })
+            .collect();
+        let kind = Kind::D2(w, h, AaMode::Single);
+        let (surface, view) = factory
+            .create_texture_immutable::<Rgba32F>(kind, Mipmap::Provided, &[&data])
+            .map_err(TextureError::Create)?;
+        let sampler = factory.create_sampler(sampler_info(settings));
+        Ok(HdrTexture {
+            surface,
+            sampler,
+            view,
+        })
+    }
+}
@@ -11,9 +11,9 @@
 //!
 //! - [Gfx](https://github.com/gfx-rs/gfx) with an OpenGL back-end.
 //! - [gfx_graphics](https://github.com/pistondevelopers/gfx_graphics)
-//! for 2D rendering.
+//!   for 2D rendering.
 //! - [glutin_window](https://github.com/pistondevelopers/glutin_window)
-//! as default window back-end, but this can be swapped (see below).
+//!   as default window back-end, but this can be swapped (see below).
 //!
 //! ### Example
 //!
@@ -80,13 +80,19 @@
 //! Instead, libraries should depend on the lower abstractions,
 //! such as the [Piston core](https://github.com/pistondevelopers/piston).
 
+#[cfg(feature = "clipboard")]
+extern crate arboard;
 extern crate gfx;
 extern crate gfx_device_gl;
+extern crate gfx_gl;
 extern crate gfx_graphics;
 extern crate piston;
+extern crate rusttype;
 extern crate shader_version;
+extern crate tracing;
 
 pub extern crate graphics;
+pub extern crate image;
 pub extern crate texture;
 
 /// Exports all of the types exposed by this module, *except* for `graphics`.
@@ -97,5 +103,22 @@ pub extern crate texture;
 /// Using the name "prelude" also suppresses the wildcard import warning from clippy.
 pub mod prelude;
 
+mod build_error;
+mod clip;
+mod depth;
+mod event_filter;
+mod file_drag;
+mod fps_counter;
+mod glyphs;
+mod hdr;
+mod offscreen;
+mod raw_texture;
+mod readback;
+mod redraw;
+mod render_profiler;
+mod sampler;
+mod sub_loop;
+mod textures;
+
 pub use graphics::*;
 pub use prelude::*;
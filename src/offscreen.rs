@@ -0,0 +1,130 @@
+//! Off-screen render targets.
+
+use gfx::format::{DepthStencil, Srgba8, R8_G8_B8_A8};
+use gfx::handle::{DepthStencilView, RenderTargetView, Texture};
+use gfx_device_gl::{Factory, Resources};
+use prelude::{G2dTexture, TextureError};
+use textures::texture_size;
+
+/// View for rendering colors.
+pub(crate) type ColorView = RenderTargetView<Resources, Srgba8>;
+/// View for rendering depth and stencil.
+pub(crate) type StencilView = DepthStencilView<Resources, DepthStencil>;
+
+/// A render target that draws into a texture instead of the window.
+///
+/// Create one with `PistonWindow::create_offscreen_target`
+/// and render into it with `PistonWindow::draw_2d_to_target`.
+/// The color texture can then be drawn to the screen with `graphics::Image`.
+#[derive(Clone)]
+pub struct OffscreenTarget {
+    /// The color texture that is rendered into.
+    pub color: G2dTexture,
+    /// The depth stencil buffer, when requested at creation.
+    pub depth: Option<DepthStencilView<Resources, DepthStencil>>,
+    /// The width in pixels.
+    pub width: u32,
+    /// The height in pixels.
+    pub height: u32,
+    /// View for rendering into the color texture.
+    pub(crate) output_color: ColorView,
+    /// Stencil buffer used by `Gfx2d` for clipping.
+    pub(crate) output_stencil: StencilView,
+}
+
+impl OffscreenTarget {
+    /// Creates a new off-screen target.
+    pub(crate) fn new(
+        factory: &mut Factory,
+        width: u32,
+        height: u32,
+        with_depth: bool,
+    ) -> Result<OffscreenTarget, TextureError> {
+        let color = create_render_texture(factory, width, height)?;
+        // `Gfx2d` always needs a stencil buffer,
+        // so it is shared with the depth buffer when one is requested.
+        let (output_color, output_stencil) = render_target_views(factory, &color.surface)?;
+
+        Ok(OffscreenTarget {
+            color,
+            depth: if with_depth {
+                Some(output_stencil.clone())
+            } else {
+                None
+            },
+            width,
+            height,
+            output_color,
+            output_stencil,
+        })
+    }
+}
+
+/// Creates a texture that can be rendered into and drawn with `G2d`.
+pub(crate) fn create_render_texture(
+    factory: &mut Factory,
+    width: u32,
+    height: u32,
+) -> Result<G2dTexture, TextureError> {
+    use gfx::format::{ChannelType, Swizzle};
+    use gfx::memory::{Bind, Usage};
+    use gfx::texture::{AaMode, FilterMethod, Kind, SamplerInfo, WrapMode};
+    use gfx::Factory;
+
+    let (w, h) = texture_size(width, height)?;
+    let surface = factory.create_texture(
+        Kind::D2(w, h, AaMode::Single),
+        1,
+        Bind::SHADER_RESOURCE | Bind::RENDER_TARGET | Bind::TRANSFER_SRC,
+        Usage::Data,
+        Some(ChannelType::Srgb),
+    )?;
+    let view =
+        factory.view_texture_as_shader_resource::<Srgba8>(&surface, (0, 0), Swizzle::new())?;
+    let sampler = factory.create_sampler(SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp));
+    Ok(G2dTexture {
+        surface,
+        sampler,
+        view,
+    })
+}
+
+/// Creates views for rendering 2D graphics into a texture.
+///
+/// Returns an error if the texture was not created as a render target.
+fn render_target_views(
+    factory: &mut Factory,
+    surface: &Texture<Resources, R8_G8_B8_A8>,
+) -> Result<(ColorView, StencilView), TextureError> {
+    let (w, h, _, _) = surface.get_info().kind.get_dimensions();
+    let output_color = render_target_view(factory, surface)?;
+    let output_stencil = stencil_view(factory, w, h)?;
+    Ok((output_color, output_stencil))
+}
+
+/// Creates a view for rendering colors into a texture.
+///
+/// Returns an error if the texture was not created as a render target.
+pub(crate) fn render_target_view(
+    factory: &mut Factory,
+    surface: &Texture<Resources, R8_G8_B8_A8>,
+) -> Result<ColorView, TextureError> {
+    use gfx::Factory;
+
+    factory
+        .view_texture_as_render_target::<Srgba8>(surface, 0, None)
+        .map_err(|err| TextureError::Create(err.into()))
+}
+
+/// Creates a stencil buffer for `Gfx2d`, which uses it for clipping.
+pub(crate) fn stencil_view(
+    factory: &mut Factory,
+    width: u16,
+    height: u16,
+) -> Result<StencilView, TextureError> {
+    use gfx::Factory;
+
+    factory
+        .create_depth_stencil_view_only::<DepthStencil>(width, height)
+        .map_err(TextureError::Create)
+}
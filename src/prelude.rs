@@ -1,14 +1,35 @@
+pub use build_error::PistonWindowBuildError;
+pub use clip::scissor_clip_context;
+pub use depth::DepthTexture;
+pub use event_filter::{EventFilter, EventFilterFn};
+pub use file_drag::FileDragEvent;
+pub use gfx::SubmissionError;
+pub use gfx_graphics::Error as TextureError;
 pub use gfx_graphics::{Filter, Flip, Texture, TextureContext, TextureSettings};
+pub use glyphs::GlyphsExt;
+pub use hdr::HdrTexture;
+pub use offscreen::OffscreenTarget;
 pub use piston::event_loop::*;
 pub use piston::input::*;
 pub use piston::window::*;
 pub use piston::*;
+pub use raw_texture::RawPixelFormat;
+pub use readback::ToImage;
+pub use render_profiler::{FrameTime, RenderProfiler};
 pub use shader_version::OpenGL;
+pub use sub_loop::{SubLoop, SubLoopControl};
+pub use textures::{generate_mipmaps, load_texture_from_bytes, texture_from_color};
 
+use clip;
+use fps_counter::FpsCounter;
 use gfx_graphics::{Gfx2d, GfxGraphics};
-use graphics::Context;
+use graphics::{Context, Viewport};
+use offscreen::{create_render_texture, render_target_view, stencil_view, ColorView, StencilView};
+use readback::read_frame_buffer;
+use redraw::{is_redraw_event, RedrawWindow};
+use sampler::anisotropic_sampler_info;
 use std::error::Error;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Actual device used by Gfx backend.
 pub type GfxDevice = gfx_device_gl::Device;
@@ -39,6 +60,9 @@ pub struct PistonWindow<W: Window> {
     /// GFX encoder.
     pub encoder: GfxEncoder,
     /// GFX device.
+    ///
+    /// Use `device.get_info()` for the renderer name, vendor and OpenGL version,
+    /// and `gfx::Device::get_capabilities` for limits and supported features.
     pub device: gfx_device_gl::Device,
     /// Output frame buffer.
     pub output_color: gfx::handle::RenderTargetView<gfx_device_gl::Resources, gfx::format::Srgba8>,
@@ -51,12 +75,39 @@ pub struct PistonWindow<W: Window> {
     pub events: Events,
     /// The factory that was created along with the device.
     pub factory: gfx_device_gl::Factory,
+    /// Render profiler, when profiling is active.
+    render_profiler: Option<RenderProfiler>,
+    /// The time when the window was created.
+    created_at: Instant,
+    /// Decides which events are returned by `next`.
+    event_filter: Option<EventFilterFn>,
+    /// Whether a frame is being rendered, from render event until buffers are swapped.
+    in_frame: bool,
+    /// Whether a redraw is requested for lazy event loops.
+    redraw_requested: bool,
+    /// Stencil buffer reused by `draw_2d_to_texture`, sized for the last texture.
+    texture_stencil: Option<StencilView>,
+    /// Error of the last submission of draw commands, if it failed.
+    last_submit_error: Option<SubmissionError>,
+    /// Measures the frame rate.
+    fps_counter: FpsCounter,
+    /// Whether the window has focus, updated from focus events.
+    focused: bool,
+    /// System clipboard, opened on first use.
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<arboard::Clipboard>,
 }
 
 #[cfg(feature = "glutin")]
 extern crate glutin_window;
 #[cfg(feature = "glutin")]
 use self::glutin_window::GlutinWindow;
+#[cfg(feature = "glutin")]
+extern crate glutin;
+#[cfg(feature = "glutin")]
+extern crate winit;
+#[cfg(feature = "glutin")]
+pub use self::winit::window::CursorIcon;
 /// Contains everything required for controlling window, graphics, event loop.
 #[cfg(feature = "glutin")]
 pub struct PistonWindow<W: Window = GlutinWindow> {
@@ -65,6 +116,9 @@ pub struct PistonWindow<W: Window = GlutinWindow> {
     /// GFX encoder.
     pub encoder: GfxEncoder,
     /// GFX device.
+    ///
+    /// Use `device.get_info()` for the renderer name, vendor and OpenGL version,
+    /// and `gfx::Device::get_capabilities` for limits and supported features.
     pub device: gfx_device_gl::Device,
     /// Output frame buffer.
     pub output_color: gfx::handle::RenderTargetView<gfx_device_gl::Resources, gfx::format::Srgba8>,
@@ -77,12 +131,40 @@ pub struct PistonWindow<W: Window = GlutinWindow> {
     pub events: Events,
     /// The factory that was created along with the device.
     pub factory: gfx_device_gl::Factory,
+    /// Render profiler, when profiling is active.
+    render_profiler: Option<RenderProfiler>,
+    /// The time when the window was created.
+    created_at: Instant,
+    /// Decides which events are returned by `next`.
+    event_filter: Option<EventFilterFn>,
+    /// Whether a frame is being rendered, from render event until buffers are swapped.
+    in_frame: bool,
+    /// Whether a redraw is requested for lazy event loops.
+    redraw_requested: bool,
+    /// Stencil buffer reused by `draw_2d_to_texture`, sized for the last texture.
+    texture_stencil: Option<StencilView>,
+    /// Error of the last submission of draw commands, if it failed.
+    last_submit_error: Option<SubmissionError>,
+    /// Measures the frame rate.
+    fps_counter: FpsCounter,
+    /// Whether the window has focus, updated from focus events.
+    focused: bool,
+    /// System clipboard, opened on first use.
+    #[cfg(feature = "clipboard")]
+    clipboard: Option<arboard::Clipboard>,
+    /// Whether the window was last set to stay on top of other windows.
+    always_on_top: bool,
+    /// Whether the window was maximized when it was minimized with `minimize`.
+    maximized_before_minimize: Option<bool>,
 }
 
 impl<W> BuildFromWindowSettings for PistonWindow<W>
 where
     W: Window + OpenGLWindow + BuildFromWindowSettings,
 {
+    /// Builds the window and creates the OpenGL device.
+    ///
+    /// Errors are `PistonWindowBuildError`, which can be matched on after downcasting.
     fn build_from_window_settings(
         settings: &WindowSettings,
     ) -> Result<PistonWindow<W>, Box<dyn Error>> {
@@ -97,9 +179,12 @@ where
         let samples = settings.get_samples();
 
         let opengl =
-            OpenGL::from_api(api).expect("Could not detect OpenGL version from graphics API");
+            OpenGL::from_api(api.clone()).ok_or(PistonWindowBuildError::UnsupportedApi(api))?;
+        let window = settings
+            .build()
+            .map_err(PistonWindowBuildError::WindowCreationFailed)?;
 
-        Ok(PistonWindow::new(opengl, samples, settings.build()?))
+        Ok(PistonWindow::new(opengl, samples, window))
     }
 }
 
@@ -121,6 +206,39 @@ fn create_main_targets(
     (output_color, output_stencil)
 }
 
+/// Submits the encoded commands to the device.
+///
+/// Logs submission errors instead of panicking, and returns them.
+/// The commands of a failed submission are dropped.
+pub(crate) fn submit(
+    encoder: &mut GfxEncoder,
+    device: &mut gfx_device_gl::Device,
+) -> Result<(), SubmissionError> {
+    let res = encoder.flush_no_reset(device);
+    if let Err(ref err) = res {
+        tracing::error!("Failed to submit commands: {}", err);
+    }
+    encoder.reset();
+    res
+}
+
+/// Logs which renderer, driver and capabilities the device is using.
+fn log_device_info(device: &gfx_device_gl::Device) {
+    use gfx::Device;
+
+    let info = device.get_info();
+    tracing::info!(
+        "OpenGL renderer: {} ({}), version {:?}, GLSL {:?}",
+        info.platform_name.renderer,
+        info.platform_name.vendor,
+        info.version,
+        info.shading_language
+    );
+    let caps = device.get_capabilities();
+    tracing::debug!("OpenGL max texture size: {}", caps.max_texture_size);
+    tracing::debug!("OpenGL capabilities: {:?}", caps);
+}
+
 impl<W> PistonWindow<W>
 where
     W: Window,
@@ -132,6 +250,7 @@ where
     {
         let (device, mut factory) =
             gfx_device_gl::create(|s| window.get_proc_address(s) as *const _);
+        log_device_info(&device);
 
         let (output_color, output_stencil) = {
             let aa = samples as gfx::texture::NumSamples;
@@ -149,18 +268,131 @@ where
         let encoder = factory.create_command_buffer().into();
         let events = Events::new(EventSettings::new());
         PistonWindow {
-            window: window,
-            encoder: encoder,
-            device: device,
-            output_color: output_color,
-            output_stencil: output_stencil,
-            g2d: g2d,
-            events: events,
-            factory: factory,
+            window,
+            encoder,
+            device,
+            output_color,
+            output_stencil,
+            g2d,
+            events,
+            factory,
+            render_profiler: None,
+            created_at: Instant::now(),
+            event_filter: None,
+            in_frame: false,
+            redraw_requested: false,
+            texture_stencil: None,
+            last_submit_error: None,
+            fps_counter: FpsCounter::new(),
+            focused: true,
+            #[cfg(feature = "clipboard")]
+            clipboard: None,
+            #[cfg(feature = "glutin")]
+            always_on_top: false,
+            #[cfg(feature = "glutin")]
+            maximized_before_minimize: None,
+        }
+    }
+
+    /// Returns the time elapsed since the window was created.
+    ///
+    /// Unlike summing `dt` of update events,
+    /// this also counts the time the event loop is paused.
+    pub fn uptime(&self) -> Duration {
+        self.created_at.elapsed()
+    }
+
+    /// Returns the ratio of physical pixels to logical pixels.
+    ///
+    /// This is larger than `1.0` on HiDPI displays.
+    /// Computed from the draw size and size of the window,
+    /// and `1.0` when the window has no size.
+    pub fn scale_factor(&self) -> f64 {
+        let size = self.window.size();
+        let draw_size = self.window.draw_size();
+        if size.width > 0.0 {
+            draw_size.width / size.width
+        } else {
+            1.0
         }
     }
 
+    /// Returns the text on the system clipboard.
+    ///
+    /// Returns `None` if the clipboard is empty, does not contain text,
+    /// or is not available on the platform.
+    #[cfg(feature = "clipboard")]
+    pub fn get_clipboard_text(&mut self) -> Option<String> {
+        self.clipboard().ok()?.get_text().ok()
+    }
+
+    /// Puts text on the system clipboard.
+    ///
+    /// On Linux, the text stays available to other applications while the window is alive.
+    #[cfg(feature = "clipboard")]
+    pub fn set_clipboard_text(&mut self, text: &str) -> Result<(), Box<dyn Error>> {
+        self.clipboard()?.set_text(text)?;
+        Ok(())
+    }
+
+    /// Opens the system clipboard on first use.
+    #[cfg(feature = "clipboard")]
+    fn clipboard(&mut self) -> Result<&mut arboard::Clipboard, arboard::Error> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new()?);
+        }
+        Ok(self.clipboard.as_mut().unwrap())
+    }
+
+    /// Returns the measured frames per second, averaged over the last 60 frames.
+    ///
+    /// Frames are counted on after render events.
+    /// Returns `0.0` until two frames are rendered.
+    pub fn current_fps(&self) -> f64 {
+        self.fps_counter.fps()
+    }
+
+    /// Returns the duration of the last frame in milliseconds.
+    pub fn frame_time_ms(&self) -> f64 {
+        self.fps_counter.last_frame_time().as_secs_f64() * 1000.0
+    }
+
+    /// Returns how far rendering is between the last and the next update, from 0 to 1.
+    ///
+    /// Computed from `RenderArgs::ext_dt` and the updates per second in the event settings.
+    /// Interpolate between the previous and current physics state with this value
+    /// for smooth rendering at any frame rate.
+    pub fn interpolation_alpha(&self, args: &RenderArgs) -> f64 {
+        let ups = self.events.get_event_settings().ups;
+        (args.ext_dt * ups as f64).clamp(0.0, 1.0)
+    }
+
+    /// Returns `true` if the window has focus.
+    ///
+    /// Updated from focus events, which can be handled with `FocusEvent::focus_args`
+    /// to react when focus changes.
+    /// The window is assumed to have focus until the first focus event.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Returns the area content should be drawn in, as `[x, y, w, h]` in logical pixels.
+    ///
+    /// The window back-ends do not report safe area insets,
+    /// so this is currently the whole window.
+    pub fn get_drawable_area(&self) -> [f64; 4] {
+        let size = self.window.size();
+        [0.0, 0.0, size.width, size.height]
+    }
+
     /// Creates context used to create and update textures.
+    ///
+    /// Can be called multiple times, and each context has its own encoder.
+    /// The contexts share the OpenGL context of the window and are not `Send`,
+    /// so textures must be created on the thread that owns the window.
+    /// To load assets in parallel, decode the images on worker threads
+    /// and upload them with `Texture::from_image` on the window thread.
+    /// Submit the encoder of the context to the device after uploading.
     pub fn create_texture_context(&mut self) -> G2dTextureContext {
         TextureContext {
             factory: self.factory.clone(),
@@ -169,24 +401,117 @@ where
     }
 
     /// Loads font from a path.
+    ///
+    /// The texture settings are used for the glyph textures,
+    /// for example to use linear filtering when rendering at fractional scales.
     pub fn load_font<P: AsRef<std::path::Path>>(
         &mut self,
         path: P,
+        settings: TextureSettings,
     ) -> Result<Glyphs, std::io::Error> {
-        Glyphs::new(
-            path,
-            TextureContext {
-                factory: self.factory.clone(),
-                encoder: self.factory.create_command_buffer().into(),
-            },
-            TextureSettings::new(),
+        let context = self.create_texture_context();
+        Glyphs::new(path, context, settings)
+    }
+
+    /// Loads font from a path, with default texture settings.
+    pub fn load_font_default<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Glyphs, std::io::Error> {
+        self.load_font(path, TextureSettings::new())
+    }
+
+    /// Loads font from memory, such as data embedded with `include_bytes!`.
+    ///
+    /// Returns an error if the data is empty or not a valid TrueType or OpenType font.
+    pub fn load_font_from_bytes(&mut self, data: &'static [u8]) -> Result<Glyphs, Box<dyn Error>> {
+        if data.is_empty() {
+            return Err("Can not load font from empty data".into());
+        }
+        let context = self.create_texture_context();
+        Glyphs::from_bytes(data, context, TextureSettings::new()).map_err(|()| {
+            "Can not load font, the data is not a valid TrueType or OpenType font".into()
+        })
+    }
+
+    /// Sets the anisotropic filtering level of a texture.
+    ///
+    /// Replaces the sampler of the texture with one using anisotropic filtering,
+    /// keeping the wrap modes and border color of the current sampler.
+    /// The level is clamped by the driver, usually to 16.
+    /// Levels below 2 turn off anisotropic filtering,
+    /// going back to the filter of the settings,
+    /// which filters between mipmap levels when the texture has mipmaps.
+    /// Other filters, such as the one set by `generate_mipmaps`, are kept.
+    pub fn set_anisotropy(&self, texture: &mut G2dTexture, settings: &TextureSettings, level: u8) {
+        use gfx::Factory;
+
+        let has_mipmaps = texture.surface.get_info().levels > 1;
+        let info =
+            anisotropic_sampler_info(*texture.sampler.get_info(), settings, level, has_mipmaps);
+        texture.sampler = self.factory.clone().create_sampler(info);
+    }
+
+    /// Creates a texture from raw pixel data, bypassing image decoding.
+    ///
+    /// The data is converted to the RGBA layout of `G2dTexture` before uploading.
+    /// Rows are tightly packed, starting with the top row.
+    pub fn load_texture_raw(
+        &self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        format: RawPixelFormat,
+        settings: &TextureSettings,
+    ) -> Result<G2dTexture, TextureError> {
+        use texture::{CreateTexture, Format};
+
+        let expected = width as usize * height as usize * format.bytes_per_pixel();
+        if data.len() != expected {
+            return Err(TextureError::Image(format!(
+                "Expected {} bytes for {}x{} {:?} pixels, got {}",
+                expected,
+                width,
+                height,
+                format,
+                data.len()
+            )));
+        }
+        let mut factory = self.factory.clone();
+        let mut context = TextureContext {
+            encoder: factory.create_command_buffer().into(),
+            factory,
+        };
+        G2dTexture::create(
+            &mut context,
+            Format::Rgba8,
+            &format.to_rgba8(data),
+            [width, height],
+            settings,
         )
     }
 
+    /// Loads a floating point texture from a path.
+    ///
+    /// Supports HDR image formats such as Radiance HDR and OpenEXR.
+    pub fn load_texture_hdr<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        settings: &TextureSettings,
+    ) -> Result<HdrTexture, TextureError> {
+        HdrTexture::from_path(&mut self.factory.clone(), path, settings)
+    }
+
     /// Renders 2D graphics.
     ///
     /// Calls the closure on render events.
     /// There is no need to filter events manually, and there is no overhead.
+    ///
+    /// The transform of the context uses window coordinates,
+    /// which are logical pixels on HiDPI displays, see `scale_factor`.
+    ///
+    /// Errors when submitting the frame to the device are logged
+    /// and the frame is dropped, instead of panicking.
     pub fn draw_2d<E, F, U>(&mut self, e: &E, f: F) -> Option<U>
     where
         W: OpenGLWindow,
@@ -195,6 +520,7 @@ where
     {
         if let Some(args) = e.render_args() {
             self.window.make_current();
+            let start = Instant::now();
             let device = &mut self.device;
             let res = self.g2d.draw(
                 &mut self.encoder,
@@ -203,13 +529,119 @@ where
                 args.viewport(),
                 |c, g| f(c, g, device),
             );
-            self.encoder.flush(device);
+            self.flush_frame(start);
+            Some(res)
+        } else {
+            None
+        }
+    }
+
+    /// Renders 2D graphics with a fallible closure.
+    ///
+    /// Works like `draw_2d`, but lets the closure return errors with `?`.
+    /// The commands recorded before an error are still submitted.
+    pub fn try_draw_2d<E, F, U, Err>(&mut self, e: &E, f: F) -> Option<Result<U, Err>>
+    where
+        W: OpenGLWindow,
+        E: GenericEvent,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> Result<U, Err>,
+    {
+        self.draw_2d(e, f)
+    }
+
+    /// Renders 2D graphics with text.
+    ///
+    /// Works like `draw_2d`, but passes the glyph cache to the closure
+    /// and submits the glyphs it uploads before the frame that draws them.
+    /// There is no need to flush `glyphs.factory.encoder` manually.
+    pub fn draw_2d_with_glyphs<E, F, U>(&mut self, e: &E, glyphs: &mut Glyphs, f: F) -> Option<U>
+    where
+        W: OpenGLWindow,
+        E: GenericEvent,
+        F: FnOnce(Context, &mut G2d, &mut Glyphs, &mut gfx_device_gl::Device) -> U,
+    {
+        if let Some(args) = e.render_args() {
+            self.window.make_current();
+            let start = Instant::now();
+            let device = &mut self.device;
+            let res = self.g2d.draw(
+                &mut self.encoder,
+                &self.output_color,
+                &self.output_stencil,
+                args.viewport(),
+                |c, g| f(c, g, glyphs, device),
+            );
+            let glyphs_res = submit(&mut glyphs.factory.encoder, &mut self.device);
+            self.flush_frame(start);
+            if let Err(err) = glyphs_res {
+                self.last_submit_error = Some(err);
+            }
             Some(res)
         } else {
             None
         }
     }
 
+    /// Renders 2D graphics clipped to a rectangle.
+    ///
+    /// Works like `draw_2d`, but sets a scissor on the draw state of the context.
+    /// The scissor is `[x, y, w, h]` in physical pixels, with origin in the upper left corner,
+    /// and is clamped to the frame buffer.
+    /// It only applies to draw calls using the draw state of the context,
+    /// so later frames are not affected.
+    pub fn draw_2d_scissored<E, F, U>(&mut self, e: &E, scissor: [u32; 4], f: F) -> Option<U>
+    where
+        W: OpenGLWindow,
+        E: GenericEvent,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> U,
+    {
+        self.draw_2d(e, |mut c, g, device| {
+            if let Some(v) = c.viewport {
+                let [w, h] = v.draw_size;
+                let rect = clip::intersect(scissor, [0, 0, w, h]);
+                c.draw_state.scissor = Some(clip::flip_y(rect, h));
+            }
+            f(c, g, device)
+        })
+    }
+
+    /// Renders 2D graphics with a fixed aspect ratio.
+    ///
+    /// Works like `draw_2d`, but scales `content_size` to the largest centered
+    /// area of the window with the same aspect ratio and fills the rest with black bars.
+    /// The context passed to the closure uses `content_size` as its coordinate system,
+    /// and drawing outside the area is clipped when using the draw state of the context.
+    ///
+    /// When a component of `content_size` is not positive,
+    /// there is no aspect ratio to keep and the closure gets the untransformed context.
+    pub fn draw_2d_letterboxed<E, F, U>(&mut self, e: &E, content_size: [f64; 2], f: F) -> Option<U>
+    where
+        W: OpenGLWindow,
+        E: GenericEvent,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> U,
+    {
+        use graphics::Transformed;
+
+        self.draw_2d(e, |c, g, device| {
+            graphics::clear([0.0, 0.0, 0.0, 1.0], g);
+            let (window_size, draw_size) = match c.viewport {
+                Some(v) => (v.window_size, v.draw_size),
+                None => return f(c, g, device),
+            };
+            if !(content_size[0] > 0.0 && content_size[1] > 0.0) {
+                return f(c, g, device);
+            }
+            let scale = (window_size[0] / content_size[0]).min(window_size[1] / content_size[1]);
+            let w = content_size[0] * scale;
+            let h = content_size[1] * scale;
+            let x = (window_size[0] - w) / 2.0;
+            let y = (window_size[1] - h) / 2.0;
+            let (c, _) = scissor_clip_context(c, draw_size, [x, y, w, h]);
+            let c = c.trans(x, y).scale(scale, scale);
+            f(c, g, device)
+        })
+    }
+
     /// Renders 3D graphics.
     ///
     /// Calls the closure on render events.
@@ -220,28 +652,343 @@ where
         E: GenericEvent,
         F: FnOnce(&mut Self) -> U,
     {
-        if let Some(_) = e.render_args() {
+        if e.render_args().is_some() {
             self.window.make_current();
+            let start = Instant::now();
             let res = f(self);
-            self.encoder.flush(&mut self.device);
+            self.flush_frame(start);
             Some(res)
         } else {
             None
         }
     }
 
+    /// Creates a render target that draws into a texture.
+    ///
+    /// When `with_depth` is `true`, the target gets a depth buffer
+    /// that can be used for 3D rendering into the texture.
+    /// Returns an error if a size is zero or larger than 65535.
+    pub fn create_offscreen_target(
+        &self,
+        width: u32,
+        height: u32,
+        with_depth: bool,
+    ) -> Result<OffscreenTarget, TextureError> {
+        OffscreenTarget::new(&mut self.factory.clone(), width, height, with_depth)
+    }
+
+    /// Creates a depth texture, for example to use with `draw_3d`.
+    ///
+    /// The texture can be sampled in shaders, unlike `output_stencil`.
+    /// Returns an error if a size is zero or larger than 65535.
+    pub fn create_depth_texture(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<DepthTexture, TextureError> {
+        DepthTexture::new(&mut self.factory.clone(), width, height)
+    }
+
+    /// Resizes a depth texture to the draw size of the window.
+    ///
+    /// Does nothing when the size is unchanged,
+    /// so it can be called on every resize or render event.
+    /// Returns `true` if the texture was recreated.
+    /// Returns an error when the draw size is zero, for example while minimized,
+    /// and the texture is kept.
+    pub fn resize_depth_texture(&self, depth: &mut DepthTexture) -> Result<bool, TextureError> {
+        let draw_size = self.window.draw_size();
+        depth.resize(
+            &mut self.factory.clone(),
+            draw_size.width as u32,
+            draw_size.height as u32,
+        )
+    }
+
+    /// Creates a texture that can be rendered into with `draw_2d_to_texture`.
+    ///
+    /// The texture can be drawn with `graphics::Image`,
+    /// and read back to the CPU with `ToImage::to_image`.
+    /// Returns an error if a size is zero or larger than 65535.
+    pub fn create_render_texture(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<G2dTexture, TextureError> {
+        create_render_texture(&mut self.factory.clone(), width, height)
+    }
+
+    /// Renders 2D graphics into an off-screen target.
+    ///
+    /// Unlike `draw_2d`, this does not wait for a render event.
+    /// The commands are submitted before returning,
+    /// so `target.color` can be drawn in the same frame.
+    pub fn draw_2d_to_target<F, U>(&mut self, target: &OffscreenTarget, f: F) -> U
+    where
+        W: OpenGLWindow,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> U,
+    {
+        self.draw_2d_to_views(
+            &target.output_color,
+            &target.output_stencil,
+            [target.width, target.height],
+            f,
+        )
+    }
+
+    /// Renders 2D graphics into a texture.
+    ///
+    /// The texture must be created as a render target, for example with
+    /// `create_offscreen_target`, otherwise an error is returned.
+    /// Like `draw_2d_to_target`, this does not wait for a render event
+    /// and submits the commands before returning.
+    ///
+    /// A render target view is created on every call.
+    /// The stencil buffer is reused while the texture size stays the same,
+    /// and recreated when textures of different sizes are drawn into.
+    /// For rendering into the same texture every frame,
+    /// `OffscreenTarget` with `draw_2d_to_target` keeps all views.
+    pub fn draw_2d_to_texture<F, U>(&mut self, target: &G2dTexture, f: F) -> Result<U, TextureError>
+    where
+        W: OpenGLWindow,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> U,
+    {
+        use gfx::memory::Typed;
+
+        let output_color = render_target_view(&mut self.factory, &target.surface)?;
+        let (w, h, _, _) = target.surface.get_info().kind.get_dimensions();
+        let cached = self.texture_stencil.take().filter(|stencil| {
+            let (sw, sh, _, _) = stencil.raw().get_dimensions();
+            (sw, sh) == (w, h)
+        });
+        let output_stencil = match cached {
+            Some(stencil) => stencil,
+            None => stencil_view(&mut self.factory, w, h)?,
+        };
+        let size = [u32::from(w), u32::from(h)];
+        let res = self.draw_2d_to_views(&output_color, &output_stencil, size, f);
+        self.texture_stencil = Some(output_stencil);
+        Ok(res)
+    }
+
+    /// Renders 2D graphics into the given views and submits the commands.
+    ///
+    /// The context is flipped vertically, so the first row of the texture
+    /// is the top of the drawing, the same as for textures created with `from_image`.
+    fn draw_2d_to_views<F, U>(
+        &mut self,
+        output_color: &ColorView,
+        output_stencil: &StencilView,
+        size: [u32; 2],
+        f: F,
+    ) -> U
+    where
+        W: OpenGLWindow,
+        F: FnOnce(Context, &mut G2d, &mut gfx_device_gl::Device) -> U,
+    {
+        self.window.make_current();
+        let viewport = Viewport {
+            rect: [0, 0, size[0] as i32, size[1] as i32],
+            draw_size: size,
+            window_size: [f64::from(size[0]), f64::from(size[1])],
+        };
+        let device = &mut self.device;
+        let res = self.g2d.draw(
+            &mut self.encoder,
+            output_color,
+            output_stencil,
+            viewport,
+            |c, g| f(clip::flip_context(c, size[1]), g, device),
+        );
+        self.flush_encoder();
+        res
+    }
+
+    /// Reads the rendered frame back into an image.
+    ///
+    /// Call this on a render event after drawing,
+    /// before the buffers are swapped by the event loop.
+    /// Returns an error when called outside of a frame.
+    pub fn take_screenshot(&mut self) -> Result<image::RgbaImage, Box<dyn Error>>
+    where
+        W: OpenGLWindow,
+    {
+        if !self.in_frame {
+            return Err("Can not take screenshot outside of a frame".into());
+        }
+        self.window.make_current();
+        let draw_size = self.window.draw_size();
+        let width = draw_size.width as u32;
+        let height = draw_size.height as u32;
+        Ok(read_frame_buffer(&mut self.device, width, height)?)
+    }
+
+    /// Records commands outside of a frame, such as copies between textures.
+    ///
+    /// Passes the factory and encoder to the closure,
+    /// and submits the commands to the device when it returns.
+    /// Works outside of render events, for example during update.
+    pub fn with_encoder<F, U>(&mut self, f: F) -> U
+    where
+        W: OpenGLWindow,
+        F: FnOnce(&mut gfx_device_gl::Factory, &mut GfxEncoder) -> U,
+    {
+        self.window.make_current();
+        let res = f(&mut self.factory, &mut self.encoder);
+        self.flush_encoder();
+        res
+    }
+
+    /// Starts capturing render timings for the given number of frames.
+    ///
+    /// Replaces any profiler that is already running.
+    /// The timings of all draw calls during a render event add up to one frame,
+    /// which is recorded after the buffers are swapped.
+    /// While profiling, each draw call waits for the GPU to finish,
+    /// so the frame rate might drop.
+    pub fn start_render_profiling(&mut self, frames: u32) {
+        self.render_profiler = Some(RenderProfiler::new(frames));
+    }
+
+    /// Returns the render profiler when all requested frames are captured.
+    ///
+    /// Returns `None` if profiling is not started or still in progress.
+    pub fn take_render_profiler(&mut self) -> Option<RenderProfiler> {
+        if self.render_profiler.as_ref().map(|p| p.is_done()) == Some(true) {
+            self.render_profiler.take()
+        } else {
+            None
+        }
+    }
+
+    /// Flushes the encoder after a draw call started at `start`.
+    fn flush_frame(&mut self, start: Instant) {
+        let profiling = self.render_profiler.as_ref().map(|p| p.is_done()) == Some(false);
+        if !profiling {
+            self.flush_encoder();
+            return;
+        }
+
+        let cpu = start.elapsed();
+        let submit = Instant::now();
+        self.flush_encoder();
+        // Wait for the GPU to measure the time spent rendering.
+        unsafe {
+            self.device.with_gl(|gl| gl.Finish());
+        }
+        if let Some(ref mut profiler) = self.render_profiler {
+            profiler.add_to_frame(FrameTime {
+                cpu,
+                gpu: submit.elapsed(),
+            });
+        }
+    }
+
+    /// Submits the encoded commands of the frame to the device.
+    fn flush_encoder(&mut self) {
+        self.last_submit_error = submit(&mut self.encoder, &mut self.device).err();
+    }
+
+    /// Returns the error of the last submission of draw commands,
+    /// or `None` if it succeeded.
+    ///
+    /// Draw commands are submitted by `draw_2d`, `draw_3d` and similar methods.
+    /// When the submission fails, the error is logged and the frame is dropped,
+    /// while the method still returns the result of the closure.
+    /// Check this after drawing to detect dropped frames.
+    pub fn last_submit_error(&self) -> Option<&SubmissionError> {
+        self.last_submit_error.as_ref()
+    }
+
+    /// Sets a filter deciding which events are returned by `next`.
+    ///
+    /// Events for which the filter returns `false` are dropped before they reach user code.
+    /// Loop events, such as render and update events, are always returned.
+    /// See `EventFilter` for common filters.
+    pub fn set_event_filter(&mut self, filter: EventFilterFn) {
+        self.event_filter = Some(filter);
+    }
+
+    /// Removes the event filter, so all events are returned by `next`.
+    pub fn clear_event_filter(&mut self) {
+        self.event_filter = None;
+    }
+
+    /// Requests a render event when using a lazy event loop.
+    ///
+    /// A lazy event loop only renders after input events.
+    /// Call this when the state changes without input, for example from a timer,
+    /// and the next call to `next` renders instead of waiting for input.
+    /// The request is cleared by the next render event,
+    /// so it has no effect when the event loop is not lazy.
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
+
+    /// Runs the event loop, calling the handler for each event.
+    ///
+    /// Returns when the window should close,
+    /// so call `set_should_close(true)` in the handler to stop the loop.
+    pub fn run<F>(&mut self, mut handler: F)
+    where
+        F: FnMut(&mut Self, &Event),
+    {
+        while let Some(e) = self.next() {
+            handler(self, &e);
+        }
+    }
+
+    /// Drains all pending input events from the window.
+    ///
+    /// Each event is handled with `event` and filtered like in `next`.
+    /// Useful in update logic that looks at all input since the last frame,
+    /// for example for gesture recognition.
+    /// The drained events are not returned again by `next`.
+    pub fn poll_all_events(&mut self) -> Vec<Event> {
+        let mut events = vec![];
+        while let Some(e) = self.window.poll_event() {
+            self.event(&e);
+            let pass = match self.event_filter {
+                Some(ref filter) => filter(&e),
+                None => true,
+            };
+            if pass {
+                events.push(e);
+            }
+        }
+        events
+    }
+
     /// Let window handle new event.
     /// Cleans up after rendering and resizes frame buffers.
     pub fn event<E: GenericEvent>(&mut self, event: &E) {
-        use gfx::memory::Typed;
         use gfx::Device;
 
-        if let Some(_) = event.after_render_args() {
+        if event.render_args().is_some() {
+            self.in_frame = true;
+            // Any render event fulfills a pending redraw request.
+            self.redraw_requested = false;
+        }
+        if let Some(focused) = event.focus_args() {
+            self.focused = focused;
+        }
+        if event.after_render_args().is_some() {
             // After swapping buffers.
             self.device.cleanup();
+            self.in_frame = false;
+            self.fps_counter.tick();
+            if let Some(ref mut profiler) = self.render_profiler {
+                profiler.end_frame();
+            }
         }
 
-        // Check whether window has resized and update the output.
+        self.check_resize();
+    }
+
+    /// Checks whether window has resized and updates the output.
+    fn check_resize(&mut self) {
+        use gfx::memory::Typed;
+
         let dim = self.output_color.raw().get_dimensions();
         let (w, h) = (dim.0, dim.1);
         let draw_size = self.window.draw_size();
@@ -259,6 +1006,281 @@ where
     }
 }
 
+/// Fullscreen mode of a window.
+#[cfg(feature = "glutin")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FullscreenMode {
+    /// A normal window.
+    Windowed,
+    /// A borderless window covering the monitor, without changing the video mode.
+    Borderless,
+    /// Exclusive fullscreen, changing the video mode of the monitor.
+    Exclusive,
+}
+
+/// Information about a monitor.
+#[cfg(feature = "glutin")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// The name of the monitor, if available.
+    pub name: Option<String>,
+    /// The position of the upper left corner in the desktop, in logical pixels.
+    pub position: Position,
+    /// The size in logical pixels.
+    pub size: Size,
+    /// The ratio of physical pixels to logical pixels.
+    pub scale_factor: f64,
+}
+
+#[cfg(feature = "glutin")]
+impl MonitorInfo {
+    fn new(monitor: &winit::monitor::MonitorHandle) -> MonitorInfo {
+        let scale_factor = monitor.scale_factor();
+        let position = monitor.position().to_logical::<i32>(scale_factor);
+        let size = monitor.size().to_logical::<f64>(scale_factor);
+        MonitorInfo {
+            name: monitor.name(),
+            position: Position {
+                x: position.x,
+                y: position.y,
+            },
+            size: Size {
+                width: size.width,
+                height: size.height,
+            },
+            scale_factor,
+        }
+    }
+}
+
+#[cfg(feature = "glutin")]
+impl PistonWindow<GlutinWindow> {
+    /// Sets the shape of the cursor when it is over the window.
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.window.window.set_cursor_icon(icon);
+    }
+
+    /// Hides the cursor when it is over the window.
+    pub fn hide_cursor(&mut self) {
+        self.window.window.set_cursor_visible(false);
+    }
+
+    /// Shows the cursor when it is over the window.
+    pub fn show_cursor(&mut self) {
+        self.window.window.set_cursor_visible(true);
+    }
+
+    /// Confines the cursor to the window, or releases it.
+    ///
+    /// Unlike `set_capture_cursor`, the cursor stays visible and moves freely inside the window.
+    /// Returns an error if the platform does not support confining the cursor,
+    /// in which case the cursor is left as it was.
+    pub fn confine_cursor(&mut self, confine: bool) -> Result<(), Box<dyn Error>> {
+        use self::winit::window::CursorGrabMode;
+
+        let mode = if confine {
+            CursorGrabMode::Confined
+        } else {
+            CursorGrabMode::None
+        };
+        self.window.window.set_cursor_grab(mode)?;
+        Ok(())
+    }
+
+    /// Maximizes the window.
+    pub fn maximize(&mut self) {
+        self.window.window.set_maximized(true);
+        self.check_resize();
+    }
+
+    /// Minimizes the window.
+    pub fn minimize(&mut self) {
+        let window = &self.window.window;
+        if self.maximized_before_minimize.is_none() {
+            self.maximized_before_minimize = Some(window.is_maximized());
+        }
+        window.set_minimized(true);
+    }
+
+    /// Restores the window to its previous state.
+    ///
+    /// A minimized window returns to the state it had before being minimized,
+    /// which might be maximized.
+    /// Otherwise, a maximized window returns to its normal size.
+    pub fn restore(&mut self) {
+        let window = &self.window.window;
+        let was_maximized = self.maximized_before_minimize.take();
+        // Not every platform reports whether the window is minimized.
+        let minimized = window.is_minimized().unwrap_or(was_maximized.is_some());
+        if minimized {
+            window.set_minimized(false);
+            if was_maximized == Some(true) {
+                window.set_maximized(true);
+            }
+        } else {
+            window.set_maximized(false);
+        }
+        self.check_resize();
+    }
+
+    /// Sets the minimum inner size of the window in logical pixels.
+    pub fn set_min_size<S: Into<Size>>(&mut self, size: S) {
+        use self::winit::dpi::LogicalSize;
+
+        let Size { width, height } = size.into();
+        self.window
+            .window
+            .set_min_inner_size(Some(LogicalSize { width, height }));
+    }
+
+    /// Sets the maximum inner size of the window in logical pixels.
+    pub fn set_max_size<S: Into<Size>>(&mut self, size: S) {
+        use self::winit::dpi::LogicalSize;
+
+        let Size { width, height } = size.into();
+        self.window
+            .window
+            .set_max_inner_size(Some(LogicalSize { width, height }));
+    }
+
+    /// Returns the size of the primary monitor in logical pixels.
+    ///
+    /// Returns `None` if the platform can not tell which monitor is the primary one.
+    pub fn get_primary_monitor_size(&self) -> Option<Size> {
+        self.window
+            .window
+            .primary_monitor()
+            .map(|monitor| MonitorInfo::new(&monitor).size)
+    }
+
+    /// Lists the monitors available to the window.
+    pub fn enumerate_monitors(&self) -> Vec<MonitorInfo> {
+        self.window
+            .window
+            .available_monitors()
+            .map(|monitor| MonitorInfo::new(&monitor))
+            .collect()
+    }
+
+    /// Moves the window to the center of the primary monitor.
+    ///
+    /// Uses the monitor the window is on when there is no primary monitor.
+    pub fn center_on_screen(&mut self) {
+        use self::winit::dpi::PhysicalPosition;
+
+        let window = &self.window.window;
+        let monitor = match window
+            .primary_monitor()
+            .or_else(|| window.current_monitor())
+        {
+            Some(monitor) => monitor,
+            None => return,
+        };
+        let pos = monitor.position();
+        let size = monitor.size();
+        let outer = window.outer_size();
+        let x = pos.x + (i64::from(size.width) - i64::from(outer.width)) as i32 / 2;
+        let y = pos.y + (i64::from(size.height) - i64::from(outer.height)) as i32 / 2;
+        window.set_outer_position(PhysicalPosition::new(x, y));
+    }
+
+    /// Sets whether the window stays on top of other windows.
+    pub fn set_always_on_top(&mut self, value: bool) {
+        use self::winit::window::WindowLevel;
+
+        let level = if value {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        };
+        self.window.window.set_window_level(level);
+        self.always_on_top = value;
+    }
+
+    /// Returns `true` if the window was last set to stay on top of other windows.
+    ///
+    /// The window back-end can not be queried for this,
+    /// so the value set by `set_always_on_top` is returned.
+    pub fn is_always_on_top(&self) -> bool {
+        self.always_on_top
+    }
+
+    /// Turns vertical synchronization on or off.
+    ///
+    /// With vsync on, swapping buffers waits for the display to refresh.
+    /// Returns an error if the driver does not support changing the swap interval.
+    pub fn set_vsync(&mut self, enabled: bool) -> Result<(), Box<dyn Error>> {
+        use self::glutin::surface::{GlSurface, SwapInterval};
+        use std::num::NonZeroU32;
+
+        let interval = if enabled {
+            SwapInterval::Wait(NonZeroU32::new(1).unwrap())
+        } else {
+            SwapInterval::DontWait
+        };
+        self.window
+            .surface
+            .set_swap_interval(&self.window.ctx, interval)?;
+        Ok(())
+    }
+
+    /// Sets the icon shown in the title bar and task bar.
+    ///
+    /// Returns an error if the image can not be used as an icon.
+    pub fn set_window_icon(&mut self, icon: &image::RgbaImage) -> Result<(), Box<dyn Error>> {
+        use self::winit::window::Icon;
+
+        let (width, height) = icon.dimensions();
+        let icon = Icon::from_rgba(icon.as_raw().clone(), width, height)?;
+        self.window.window.set_window_icon(Some(icon));
+        Ok(())
+    }
+
+    /// Switches between windowed and fullscreen mode.
+    ///
+    /// Uses the monitor the window is on.
+    /// Exclusive fullscreen picks the video mode with the highest resolution and refresh rate,
+    /// and falls back to borderless fullscreen if the monitor is unknown.
+    pub fn set_fullscreen(&mut self, mode: FullscreenMode) {
+        use self::winit::window::Fullscreen;
+
+        let window = &self.window.window;
+        let fullscreen = match mode {
+            FullscreenMode::Windowed => None,
+            FullscreenMode::Borderless => Some(Fullscreen::Borderless(None)),
+            FullscreenMode::Exclusive => {
+                let video_mode = window.current_monitor().and_then(|monitor| {
+                    monitor.video_modes().max_by_key(|v| {
+                        let size = v.size();
+                        (size.width * size.height, v.refresh_rate_millihertz())
+                    })
+                });
+                match video_mode {
+                    Some(v) => Some(Fullscreen::Exclusive(v)),
+                    None => Some(Fullscreen::Borderless(None)),
+                }
+            }
+        };
+        window.set_fullscreen(fullscreen);
+        self.check_resize();
+    }
+}
+
+impl<W> std::fmt::Debug for PistonWindow<W>
+where
+    W: Window,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("PistonWindow")
+            .field("size", &self.window.size())
+            .field("draw_size", &self.window.draw_size())
+            .field("should_close", &self.window.should_close())
+            .field("renderer", &self.device.get_info().platform_name)
+            .field("event_settings", &self.events.get_event_settings())
+            .finish_non_exhaustive()
+    }
+}
+
 impl<W> Window for PistonWindow<W>
 where
     W: Window,
@@ -353,11 +1375,22 @@ where
     /// Returns next event.
     /// Cleans up after rendering and resizes frame buffers.
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(e) = self.events.next(&mut self.window) {
+        loop {
+            let e = self.events.next(&mut RedrawWindow {
+                window: &mut self.window,
+                redraw_requested: &mut self.redraw_requested,
+            })?;
+            if is_redraw_event(&e) {
+                continue;
+            }
             self.event(&e);
-            Some(e)
-        } else {
-            None
+            let pass = match (&e, &self.event_filter) {
+                (&Event::Loop(_), _) | (_, None) => true,
+                (_, Some(filter)) => filter(&e),
+            };
+            if pass {
+                return Some(e);
+            }
         }
     }
 }
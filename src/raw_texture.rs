@@ -0,0 +1,84 @@
+//! Textures from raw pixel data.
+
+use std::borrow::Cow;
+
+/// Layout of raw pixel data, with 8 bits per channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RawPixelFormat {
+    /// `(red, green, blue, alpha)`.
+    Rgba8,
+    /// `(blue, green, red, alpha)`, as produced by many cameras and video decoders.
+    Bgra8,
+    /// `(red, green, blue)`, with full opacity.
+    Rgb8,
+    /// Gray scale, with full opacity.
+    Gray8,
+}
+
+impl RawPixelFormat {
+    /// Returns the number of bytes per pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            RawPixelFormat::Rgba8 | RawPixelFormat::Bgra8 => 4,
+            RawPixelFormat::Rgb8 => 3,
+            RawPixelFormat::Gray8 => 1,
+        }
+    }
+
+    /// Converts pixel data to `(red, green, blue, alpha)`.
+    pub(crate) fn to_rgba8(self, data: &[u8]) -> Cow<'_, [u8]> {
+        let n = self.bytes_per_pixel();
+        match self {
+            RawPixelFormat::Rgba8 => Cow::Borrowed(data),
+            RawPixelFormat::Bgra8 => data
+                .chunks(n)
+                .flat_map(|p| [p[2], p[1], p[0], p[3]])
+                .collect(),
+            RawPixelFormat::Rgb8 => data
+                .chunks(n)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            RawPixelFormat::Gray8 => data.iter().flat_map(|&v| [v, v, v, 255]).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rgba8_is_borrowed() {
+        let data = [1, 2, 3, 4];
+        let rgba = RawPixelFormat::Rgba8.to_rgba8(&data);
+        assert!(matches!(rgba, Cow::Borrowed(_)));
+        assert_eq!(&*rgba, &data);
+    }
+
+    #[test]
+    fn bgra8_swaps_red_and_blue() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(
+            &*RawPixelFormat::Bgra8.to_rgba8(&data),
+            &[3, 2, 1, 4, 7, 6, 5, 8]
+        );
+    }
+
+    #[test]
+    fn rgb8_adds_opaque_alpha() {
+        let data = [1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            &*RawPixelFormat::Rgb8.to_rgba8(&data),
+            &[1, 2, 3, 255, 4, 5, 6, 255]
+        );
+    }
+
+    #[test]
+    fn gray8_expands_to_rgb() {
+        let data = [7, 200];
+        assert_eq!(
+            &*RawPixelFormat::Gray8.to_rgba8(&data),
+            &[7, 7, 7, 255, 200, 200, 200, 255]
+        );
+    }
+}
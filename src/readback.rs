@@ -0,0 +1,100 @@
+//! Reading textures back from the GPU.
+
+use gfx::memory::Typed;
+use gfx_device_gl::{Device, NewTexture};
+use gfx_gl as gl;
+use image::RgbaImage;
+use prelude::{G2dTexture, TextureError};
+
+/// Reads the pixels of a texture back to the CPU.
+pub trait ToImage {
+    /// Copies the texture into an image.
+    ///
+    /// This is the inverse of `Texture::from_image`,
+    /// and render textures drawn with `PistonWindow::draw_2d_to_texture`
+    /// have the same orientation.
+    ///
+    /// Waits for the GPU to finish rendering to the texture.
+    fn to_image(&self, device: &mut Device) -> Result<RgbaImage, TextureError>;
+}
+
+impl ToImage for G2dTexture {
+    fn to_image(&self, device: &mut Device) -> Result<RgbaImage, TextureError> {
+        let name = match *self.surface.raw().resource() {
+            NewTexture::Texture(name) => name,
+            NewTexture::Surface(_) => {
+                return Err(TextureError::Image(
+                    "Can not read back a texture without shader storage".into(),
+                ))
+            }
+        };
+        let (w, h, _, _) = self.surface.get_info().kind.get_dimensions();
+        let mut data = vec![0u8; w as usize * h as usize * 4];
+        let mut status = gl::FRAMEBUFFER_COMPLETE;
+        unsafe {
+            device.with_gl(|gl| {
+                let mut prev = 0;
+                gl.GetIntegerv(gl::READ_FRAMEBUFFER_BINDING, &mut prev);
+                let mut fbo = 0;
+                gl.GenFramebuffers(1, &mut fbo);
+                gl.BindFramebuffer(gl::READ_FRAMEBUFFER, fbo);
+                gl.FramebufferTexture2D(
+                    gl::READ_FRAMEBUFFER,
+                    gl::COLOR_ATTACHMENT0,
+                    gl::TEXTURE_2D,
+                    name,
+                    0,
+                );
+                status = gl.CheckFramebufferStatus(gl::READ_FRAMEBUFFER);
+                if status == gl::FRAMEBUFFER_COMPLETE {
+                    read_pixels(gl, w as u32, h as u32, &mut data);
+                }
+                gl.BindFramebuffer(gl::READ_FRAMEBUFFER, prev as u32);
+                gl.DeleteFramebuffers(1, &fbo);
+            });
+        }
+        if status != gl::FRAMEBUFFER_COMPLETE {
+            return Err(TextureError::Image(format!(
+                "Can not read back texture, frame buffer status {:#x}",
+                status
+            )));
+        }
+        RgbaImage::from_raw(w as u32, h as u32, data)
+            .ok_or_else(|| TextureError::Image("Pixel buffer has wrong size".into()))
+    }
+}
+
+/// Reads the back buffer of the window frame buffer into an image.
+///
+/// The rows are flipped, so the top row of the frame comes first.
+pub(crate) fn read_frame_buffer(
+    device: &mut Device,
+    width: u32,
+    height: u32,
+) -> Result<RgbaImage, TextureError> {
+    let mut data = vec![0u8; width as usize * height as usize * 4];
+    unsafe {
+        device.with_gl(|gl| {
+            gl.BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+            gl.ReadBuffer(gl::BACK);
+            read_pixels(gl, width, height, &mut data);
+        });
+    }
+    let img = RgbaImage::from_raw(width, height, data)
+        .ok_or_else(|| TextureError::Image("Pixel buffer has wrong size".into()))?;
+    Ok(image::imageops::flip_vertical(&img))
+}
+
+/// Reads RGBA pixels from the bound read frame buffer.
+unsafe fn read_pixels(gl: &gl::Gl, width: u32, height: u32, data: &mut [u8]) {
+    gl.PixelStorei(gl::PACK_ALIGNMENT, 1);
+    gl.ReadPixels(
+        0,
+        0,
+        width as i32,
+        height as i32,
+        gl::RGBA,
+        gl::UNSIGNED_BYTE,
+        data.as_mut_ptr() as *mut _,
+    );
+}
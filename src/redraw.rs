@@ -0,0 +1,58 @@
+//! Redraw requests for lazy event loops.
+
+use piston::input::event_id::EventId;
+use piston::input::Event;
+use piston::window::{Size, Window};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Identifies the event that wakes up the event loop for a requested redraw.
+const REDRAW: EventId = EventId("piston_window/redraw");
+
+/// Wraps a window to wake up the event loop when a redraw is requested.
+///
+/// Instead of waiting for the next input event,
+/// `wait_event` returns a redraw event, which makes the event loop render.
+pub(crate) struct RedrawWindow<'a, W: 'a> {
+    pub window: &'a mut W,
+    pub redraw_requested: &'a mut bool,
+}
+
+impl<'a, W: Window> Window for RedrawWindow<'a, W> {
+    fn set_should_close(&mut self, value: bool) {
+        self.window.set_should_close(value)
+    }
+    fn should_close(&self) -> bool {
+        self.window.should_close()
+    }
+    fn size(&self) -> Size {
+        self.window.size()
+    }
+    fn swap_buffers(&mut self) {
+        self.window.swap_buffers()
+    }
+    fn wait_event(&mut self) -> Event {
+        if *self.redraw_requested {
+            *self.redraw_requested = false;
+            return Event::Custom(REDRAW, Arc::new(()), None);
+        }
+        self.window.wait_event()
+    }
+    fn wait_event_timeout(&mut self, timeout: Duration) -> Option<Event> {
+        self.window.wait_event_timeout(timeout)
+    }
+    fn poll_event(&mut self) -> Option<Event> {
+        self.window.poll_event()
+    }
+    fn draw_size(&self) -> Size {
+        self.window.draw_size()
+    }
+}
+
+/// Returns `true` if the event was emitted for a requested redraw.
+pub(crate) fn is_redraw_event(e: &Event) -> bool {
+    match *e {
+        Event::Custom(id, _, _) => id == REDRAW,
+        _ => false,
+    }
+}
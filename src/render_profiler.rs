@@ -0,0 +1,205 @@
+//! Render profiling over a fixed number of frames.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Timings captured for a single frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameTime {
+    /// Time spent on the CPU recording draw commands.
+    pub cpu: Duration,
+    /// Time spent submitting the commands and waiting for the GPU to finish.
+    pub gpu: Duration,
+}
+
+/// Stores frame timings captured by `PistonWindow::start_render_profiling`.
+#[derive(Clone, Debug)]
+pub struct RenderProfiler {
+    frames: u32,
+    /// Timings added up for the frame in progress.
+    current: Option<FrameTime>,
+    /// The captured frame timings, in the order they were rendered.
+    pub frame_times: Vec<FrameTime>,
+}
+
+impl RenderProfiler {
+    /// Creates a new profiler capturing the given number of frames.
+    pub fn new(frames: u32) -> RenderProfiler {
+        RenderProfiler {
+            frames,
+            current: None,
+            frame_times: Vec::with_capacity(frames as usize),
+        }
+    }
+
+    /// Returns the number of frames to capture.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// Returns `true` when the requested number of frames has been captured.
+    pub fn is_done(&self) -> bool {
+        self.frame_times.len() >= self.frames as usize
+    }
+
+    /// Records timings for a frame.
+    ///
+    /// Frames recorded after the profiler is done are ignored.
+    pub fn record(&mut self, frame_time: FrameTime) {
+        if !self.is_done() {
+            self.frame_times.push(frame_time);
+        }
+    }
+
+    /// Adds timings of a draw call to the frame in progress.
+    pub(crate) fn add_to_frame(&mut self, frame_time: FrameTime) {
+        self.current = Some(match self.current {
+            Some(current) => FrameTime {
+                cpu: current.cpu + frame_time.cpu,
+                gpu: current.gpu + frame_time.gpu,
+            },
+            None => frame_time,
+        });
+    }
+
+    /// Records the timings added up for the frame in progress, if any.
+    pub(crate) fn end_frame(&mut self) {
+        if let Some(frame_time) = self.current.take() {
+            self.record(frame_time);
+        }
+    }
+
+    /// Returns the minimum CPU and GPU times.
+    pub fn min(&self) -> Option<FrameTime> {
+        self.fold(|a, b| a.min(b))
+    }
+
+    /// Returns the maximum CPU and GPU times.
+    pub fn max(&self) -> Option<FrameTime> {
+        self.fold(|a, b| a.max(b))
+    }
+
+    /// Returns the average CPU and GPU times.
+    pub fn average(&self) -> Option<FrameTime> {
+        let n = self.frame_times.len() as u32;
+        let sum = self.fold(|a, b| a + b)?;
+        Some(FrameTime {
+            cpu: sum.cpu / n,
+            gpu: sum.gpu / n,
+        })
+    }
+
+    /// Formats the captured timings as a table, in milliseconds.
+    pub fn report(&self) -> String {
+        fn ms(dur: Duration) -> f64 {
+            dur.as_secs_f64() * 1000.0
+        }
+
+        let mut s = String::new();
+        let _ = writeln!(s, "{:>8} {:>10} {:>10}", "frame", "cpu (ms)", "gpu (ms)");
+        for (i, frame) in self.frame_times.iter().enumerate() {
+            let _ = writeln!(
+                s,
+                "{:>8} {:>10.3} {:>10.3}",
+                i,
+                ms(frame.cpu),
+                ms(frame.gpu)
+            );
+        }
+        let summary = [
+            ("min", self.min()),
+            ("max", self.max()),
+            ("average", self.average()),
+        ];
+        for &(name, frame) in &summary {
+            if let Some(frame) = frame {
+                let _ = writeln!(
+                    s,
+                    "{:>8} {:>10.3} {:>10.3}",
+                    name,
+                    ms(frame.cpu),
+                    ms(frame.gpu)
+                );
+            }
+        }
+        s
+    }
+
+    fn fold<F>(&self, f: F) -> Option<FrameTime>
+    where
+        F: Fn(Duration, Duration) -> Duration,
+    {
+        let mut iter = self.frame_times.iter();
+        let first = *iter.next()?;
+        Some(iter.fold(first, |acc, frame| FrameTime {
+            cpu: f(acc.cpu, frame.cpu),
+            gpu: f(acc.gpu, frame.gpu),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(cpu_ms: u64, gpu_ms: u64) -> FrameTime {
+        FrameTime {
+            cpu: Duration::from_millis(cpu_ms),
+            gpu: Duration::from_millis(gpu_ms),
+        }
+    }
+
+    #[test]
+    fn empty_profiler_has_no_summary() {
+        let profiler = RenderProfiler::new(3);
+        assert!(!profiler.is_done());
+        assert_eq!(profiler.min(), None);
+        assert_eq!(profiler.max(), None);
+        assert_eq!(profiler.average(), None);
+    }
+
+    #[test]
+    fn min_max_average() {
+        let mut profiler = RenderProfiler::new(3);
+        profiler.record(frame(2, 9));
+        profiler.record(frame(6, 3));
+        profiler.record(frame(4, 6));
+        assert!(profiler.is_done());
+        assert_eq!(profiler.min(), Some(frame(2, 3)));
+        assert_eq!(profiler.max(), Some(frame(6, 9)));
+        assert_eq!(profiler.average(), Some(frame(4, 6)));
+    }
+
+    #[test]
+    fn ignores_frames_after_done() {
+        let mut profiler = RenderProfiler::new(1);
+        profiler.record(frame(1, 1));
+        profiler.record(frame(100, 100));
+        assert_eq!(profiler.frame_times, vec![frame(1, 1)]);
+    }
+
+    #[test]
+    fn draw_calls_add_up_to_one_frame() {
+        let mut profiler = RenderProfiler::new(2);
+        profiler.add_to_frame(frame(1, 2));
+        profiler.add_to_frame(frame(3, 4));
+        profiler.end_frame();
+        // Frames without draw calls are not recorded.
+        profiler.end_frame();
+        assert_eq!(profiler.frame_times, vec![frame(4, 6)]);
+        assert!(!profiler.is_done());
+    }
+
+    #[test]
+    fn report_lists_frames_and_summary() {
+        let mut profiler = RenderProfiler::new(2);
+        profiler.record(frame(1, 2));
+        profiler.record(frame(3, 4));
+        let report = profiler.report();
+        let lines: Vec<&str> = report.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "   frame   cpu (ms)   gpu (ms)");
+        assert_eq!(lines[1], "       0      1.000      2.000");
+        assert_eq!(lines[5], " average      2.000      3.000");
+    }
+}
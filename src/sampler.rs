@@ -0,0 +1,93 @@
+//! Texture sampler helpers.
+
+use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
+use texture::{Filter, TextureSettings, Wrap};
+
+/// Converts texture settings to sampler info, the same way as `gfx_texture`.
+pub(crate) fn sampler_info(settings: &TextureSettings) -> SamplerInfo {
+    // gfx has only one filter setting for both minification and magnification.
+    let filter = match settings.get_mag() {
+        Filter::Nearest => FilterMethod::Scale,
+        Filter::Linear => FilterMethod::Bilinear,
+    };
+    let mut info = SamplerInfo::new(filter, wrap_mode(settings.get_wrap_u()));
+    info.wrap_mode.1 = wrap_mode(settings.get_wrap_v());
+    info.border = settings.get_border_color().into();
+    info
+}
+
+/// Changes the anisotropic filtering level of a sampler.
+///
+/// Levels below 2 turn off anisotropic filtering.
+/// When anisotropic filtering was on, the filter of the settings is used instead,
+/// filtering between mipmap levels when the texture has mipmaps.
+/// Other filters, wrap modes and the border color are kept.
+pub(crate) fn anisotropic_sampler_info(
+    mut info: SamplerInfo,
+    settings: &TextureSettings,
+    level: u8,
+    has_mipmaps: bool,
+) -> SamplerInfo {
+    if level > 1 {
+        info.filter = FilterMethod::Anisotropic(level);
+    } else if let FilterMethod::Anisotropic(_) = info.filter {
+        let filter = sampler_info(settings).filter;
+        info.filter = if has_mipmaps {
+            mipmap_filter(filter)
+        } else {
+            filter
+        };
+    }
+    info
+}
+
+/// Returns the filter that also filters between mipmap levels.
+pub(crate) fn mipmap_filter(filter: FilterMethod) -> FilterMethod {
+    match filter {
+        FilterMethod::Scale => FilterMethod::Mipmap,
+        FilterMethod::Bilinear => FilterMethod::Trilinear,
+        filter => filter,
+    }
+}
+
+fn wrap_mode(wrap: Wrap) -> WrapMode {
+    match wrap {
+        Wrap::ClampToEdge => WrapMode::Clamp,
+        Wrap::ClampToBorder => WrapMode::Border,
+        Wrap::Repeat => WrapMode::Tile,
+        Wrap::MirroredRepeat => WrapMode::Mirror,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn low_level_keeps_mipmap_filter() {
+        let mut info = sampler_info(&TextureSettings::new());
+        info.filter = FilterMethod::Trilinear;
+        let info = anisotropic_sampler_info(info, &TextureSettings::new(), 1, true);
+        assert_eq!(info.filter, FilterMethod::Trilinear);
+    }
+
+    #[test]
+    fn turning_off_anisotropy_uses_settings_filter() {
+        let settings = TextureSettings::new();
+        let info = anisotropic_sampler_info(sampler_info(&settings), &settings, 8, true);
+        assert_eq!(info.filter, FilterMethod::Anisotropic(8));
+        let mipmapped = anisotropic_sampler_info(info, &settings, 0, true);
+        assert_eq!(mipmapped.filter, FilterMethod::Trilinear);
+        let single = anisotropic_sampler_info(info, &settings, 0, false);
+        assert_eq!(single.filter, FilterMethod::Bilinear);
+    }
+
+    #[test]
+    fn anisotropy_keeps_wrap_mode() {
+        let mut info = sampler_info(&TextureSettings::new());
+        info.wrap_mode = (WrapMode::Tile, WrapMode::Mirror, WrapMode::Clamp);
+        let info = anisotropic_sampler_info(info, &TextureSettings::new(), 4, false);
+        assert_eq!(info.wrap_mode.0, WrapMode::Tile);
+        assert_eq!(info.wrap_mode.1, WrapMode::Mirror);
+    }
+}
@@ -0,0 +1,60 @@
+//! Nested event loops.
+
+use piston::event_loop::{EventLoop, EventSettings};
+use piston::input::Event;
+use piston::window::Window;
+use prelude::PistonWindow;
+
+/// Tells a `SubLoop` whether to keep running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubLoopControl {
+    /// Continue with the next event.
+    Continue,
+    /// Leave the sub loop and return to the outer loop.
+    Break,
+}
+
+/// Runs an event loop inside another event loop,
+/// for example for a menu or a dialog.
+///
+/// The outer loop continues with the next event after the sub loop returns.
+#[derive(Clone, Debug, Default)]
+pub struct SubLoop {
+    event_settings: Option<EventSettings>,
+}
+
+impl SubLoop {
+    /// Creates a new sub loop using the event settings of the window.
+    pub fn new() -> SubLoop {
+        SubLoop {
+            event_settings: None,
+        }
+    }
+
+    /// Sets event settings to use while the sub loop runs.
+    ///
+    /// The previous event settings of the window are restored afterwards.
+    pub fn event_settings(mut self, settings: EventSettings) -> Self {
+        self.event_settings = Some(settings);
+        self
+    }
+
+    /// Runs the sub loop until the handler returns `SubLoopControl::Break`
+    /// or the window should close.
+    pub fn run<W, F>(&mut self, window: &mut PistonWindow<W>, mut handler: F)
+    where
+        W: Window,
+        F: FnMut(&mut PistonWindow<W>, &Event) -> SubLoopControl,
+    {
+        let outer_settings = window.get_event_settings();
+        if let Some(settings) = self.event_settings {
+            window.set_event_settings(settings);
+        }
+        while let Some(e) = window.next() {
+            if handler(window, &e) == SubLoopControl::Break {
+                break;
+            }
+        }
+        window.set_event_settings(outer_settings);
+    }
+}
@@ -0,0 +1,111 @@
+//! Helpers for creating textures.
+
+use gfx_device_gl::Device;
+use prelude::{submit, G2dTexture, G2dTextureContext, TextureError, TextureSettings, ToImage};
+use sampler::mipmap_filter;
+use std::convert::TryFrom;
+use std::error::Error;
+
+/// Creates a texture from encoded image data, such as PNG or JPEG bytes.
+///
+/// Useful for images embedded with `include_bytes!`.
+pub fn load_texture_from_bytes(
+    context: &mut G2dTextureContext,
+    data: &[u8],
+    settings: &TextureSettings,
+) -> Result<G2dTexture, Box<dyn Error>> {
+    let img = image::load_from_memory(data)?.to_rgba8();
+    Ok(G2dTexture::from_image(context, &img, settings)?)
+}
+
+/// Creates a 1x1 texture filled with a color, with `[r, g, b, a]` from 0 to 255.
+///
+/// Useful as a placeholder for pipelines that always sample a texture.
+/// Create it once and reuse it, instead of creating a new one each frame.
+pub fn texture_from_color(
+    context: &mut G2dTextureContext,
+    color: [u8; 4],
+    settings: &TextureSettings,
+) -> Result<G2dTexture, Box<dyn Error>> {
+    let img = image::RgbaImage::from_pixel(1, 1, image::Rgba(color));
+    Ok(G2dTexture::from_image(context, &img, settings)?)
+}
+
+/// Recreates a texture with a full mipmap chain and generates the smaller levels.
+///
+/// Textures created with `Texture::from_image` have a single level,
+/// which causes aliasing when they are drawn smaller than their size.
+/// The sampler is changed to filter between mipmap levels.
+/// Does nothing if the texture already has mipmaps.
+///
+/// The texture is read back to the CPU, so this should be done when loading,
+/// not every frame.
+pub fn generate_mipmaps(
+    context: &mut G2dTextureContext,
+    device: &mut Device,
+    texture: &mut G2dTexture,
+) -> Result<(), Box<dyn Error>> {
+    use gfx::format::{ChannelType, Srgba8, Swizzle};
+    use gfx::memory::Typed;
+    use gfx::texture::{Info, Mipmap};
+    use gfx::Factory;
+
+    let info = *texture.surface.get_info();
+    if info.levels > 1 {
+        return Ok(());
+    }
+    let img = texture.to_image(device)?;
+    let (w, h, _, _) = info.kind.get_dimensions();
+    let levels = 16 - w.max(h).max(1).leading_zeros() as u8;
+    let raw = context.factory.create_texture_raw(
+        Info { levels, ..info },
+        Some(ChannelType::Srgb),
+        Some((&[&img], Mipmap::Allocated)),
+    )?;
+    let surface = Typed::new(raw);
+    let view = context.factory.view_texture_as_shader_resource::<Srgba8>(
+        &surface,
+        (0, levels - 1),
+        Swizzle::new(),
+    )?;
+    let mut sampler_info = *texture.sampler.get_info();
+    sampler_info.filter = mipmap_filter(sampler_info.filter);
+    context.encoder.generate_mipmap(&view);
+    submit(&mut context.encoder, device)?;
+
+    texture.sampler = context.factory.create_sampler(sampler_info);
+    texture.surface = surface;
+    texture.view = view;
+    Ok(())
+}
+
+/// Checks that a texture size is supported by Gfx and returns it as `(width, height)`.
+///
+/// Returns an error for zero sizes and sizes that do not fit in 16 bits.
+pub(crate) fn texture_size(width: u32, height: u32) -> Result<(u16, u16), TextureError> {
+    match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(w), Ok(h)) if w > 0 && h > 0 => Ok((w, h)),
+        _ => Err(TextureError::Image(format!(
+            "Unsupported texture size {}x{}",
+            width, height
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn texture_size_in_range() {
+        assert_eq!(texture_size(1, 65535).unwrap(), (1, 65535));
+    }
+
+    #[test]
+    fn texture_size_out_of_range() {
+        assert!(texture_size(70000, 64).is_err());
+        assert!(texture_size(64, 65536).is_err());
+        assert!(texture_size(0, 64).is_err());
+        assert!(texture_size(64, 0).is_err());
+    }
+}